@@ -1,6 +1,6 @@
 use std::sync::mpsc::{self, TryRecvError};
 use std::thread;
-use std::time::Duration;
+use std::time::Instant;
 
 use kiss3d::window::Window;
 use nalgebra::Point3;
@@ -8,9 +8,25 @@ use tungstenite::connect;
 use url::Url;
 
 use harp::lidar;
+use harp::viewer::{FrameRateGovernor, RenderStats, DEFAULT_OUTPUT_FPS, STATS_LOG_INTERVAL};
 use kiss3d::pollster::block_on;
 
+const DEFAULT_DOWNSAMPLE_LIMIT: usize = 10000;
+
+fn env_f32(var: &str, default: f32) -> f32 {
+    std::env::var(var).ok().and_then(|v| v.parse::<f32>().ok()).unwrap_or(default)
+}
+
+fn env_usize(var: &str, default: usize) -> usize {
+    std::env::var(var).ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(default)
+}
+
 fn main() {
+    let downsample_limit = env_usize("LIDAR_VIEWER_DOWNSAMPLE", DEFAULT_DOWNSAMPLE_LIMIT);
+    let output_fps = env_f32("LIDAR_VIEWER_FPS", DEFAULT_OUTPUT_FPS);
+    let mut governor = FrameRateGovernor::new(output_fps);
+    let stats = RenderStats::default();
+
     // Channel for incoming binary frames
     let (tx, rx) = mpsc::channel::<Vec<u8>>();
 
@@ -42,13 +58,21 @@ fn main() {
     let mut points_mesh = Vec::<Point3<f32>>::new();
     let mut colors_mesh = Vec::<[u8;3]>::new();
 
-    // main render loop
+    let mut last_log = Instant::now();
+    let mut frames_in_at_last_log = 0u64;
+
+    // main render loop, paced to `output_fps` independent of how fast the server pushes data
     while block_on(window.render()) {
         // Try to get latest frame (non-blocking), process only latest available
         let mut latest_buf: Option<Vec<u8>> = None;
         loop {
             match rx.try_recv() {
-                Ok(b) => latest_buf = Some(b),
+                Ok(b) => {
+                    stats.record_in();
+                    if latest_buf.replace(b).is_some() {
+                        stats.record_dropped();
+                    }
+                }
                 Err(TryRecvError::Empty) => break,
                 Err(TryRecvError::Disconnected) => break,
             }
@@ -59,7 +83,7 @@ fn main() {
             match lidar::parse_frame(&buf) {
                 Ok((_hdr, pts, maybe_confs)) => {
                     // convert to Point3 and downsample if too many
-                    let limit = 10000usize.min(pts.len());
+                    let limit = downsample_limit.min(pts.len());
                     points_mesh.clear();
                     colors_mesh.clear();
                     let step = (pts.len() as f32 / limit as f32).max(1.0) as usize;
@@ -78,7 +102,7 @@ fn main() {
                     // try JSON lidar
                     match lidar::parse_lidar_json(&buf) {
                         Ok((pts, maybe_colors)) => {
-                            let limit = 10000usize.min(pts.len());
+                            let limit = downsample_limit.min(pts.len());
                             points_mesh.clear();
                             colors_mesh.clear();
                             let step = (pts.len() as f32 / limit as f32).max(1.0) as usize;
@@ -107,8 +131,20 @@ fn main() {
             };
             window.draw_point(p, &color);
         }
+        stats.record_rendered();
+
+        if last_log.elapsed() >= STATS_LOG_INTERVAL {
+            let frames_in = stats.frames_in();
+            let input_hz = (frames_in - frames_in_at_last_log) as f32 / last_log.elapsed().as_secs_f32();
+            println!(
+                "Viewer stats: input={:.1}Hz in={} rendered={} dropped={}",
+                input_hz, frames_in, stats.frames_rendered(), stats.frames_dropped()
+            );
+            frames_in_at_last_log = frames_in;
+            last_log = Instant::now();
+        }
 
-        // Sleep a bit to avoid hogging CPU when no frames
-        thread::sleep(Duration::from_millis(8));
+        // Pace rendering to the configured output rate rather than a fixed busy-poll sleep.
+        governor.pace();
     }
 }
@@ -0,0 +1,5 @@
+pub mod lidar;
+pub mod live_config;
+pub mod sink;
+pub mod transform;
+pub mod viewer;
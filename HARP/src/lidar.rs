@@ -1,7 +1,10 @@
 use anyhow::Result;
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::Deserialize;
-use std::io::Cursor;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Read};
+use std::time::{Duration, Instant};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Header {
@@ -19,12 +22,26 @@ pub struct Header {
     pub compression: Option<String>,
 }
 
+/// Where `LidarFrame::colors` came from. Transforms that only make sense for
+/// one provenance (e.g. `IntensityTransformer`, which rewrites colors from a
+/// single confidence byte) key off this instead of assuming every frame's
+/// colors are confidence-derived grayscale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorSource {
+    /// `colors[i] == [c, c, c]` where `c` is the original confidence byte.
+    Confidence,
+    /// `colors` are real RGB supplied by the client (e.g. the JSON lidar path).
+    Rgb,
+}
+
 /// Parse a single framed message from the binary format:
 /// [u32 header_len_le][header_json bytes][payload bytes]
 /// Returns (header, points, optional confidences)
+#[derive(Clone)]
 pub struct LidarFrame {
     pub points: Vec<[f32;3]>,
     pub colors: Option<Vec<[u8;3]>>,
+    pub color_source: ColorSource,
     pub timestamp: Option<u64>,
     pub frame_id: Option<String>,
 }
@@ -37,13 +54,12 @@ pub fn parse_frame(buf: &[u8]) -> Result<(Header, Vec<[f32; 3]>, Option<Vec<u8>>
     }
     let header_bytes = &buf[4..4 + header_len];
     let header: Header = serde_json::from_slice(header_bytes)?;
-    let payload = &buf[4 + header_len..];
+    let payload = decompress_payload(&header, &buf[4 + header_len..])?;
 
-    // For now, don't handle compression in these tests (could be extended)
     match header.layout.as_str() {
         "float32_xyz" => {
             // payload is consecutive float32 x,y,z
-            let mut pcur = Cursor::new(payload);
+            let mut pcur = Cursor::new(payload.as_ref());
             let mut points = Vec::with_capacity(header.count);
             for _ in 0..header.count {
                 let x = pcur.read_f32::<LittleEndian>()?;
@@ -55,7 +71,7 @@ pub fn parse_frame(buf: &[u8]) -> Result<(Header, Vec<[f32; 3]>, Option<Vec<u8>>
         }
         "float32_xyz_conf" => {
             // stride expected e.g. 16: 3*4 bytes + 1 byte conf + 3 bytes padding
-            let mut pcur = Cursor::new(payload);
+            let mut pcur = Cursor::new(payload.as_ref());
             let mut points = Vec::with_capacity(header.count);
             let mut confs = Vec::with_capacity(header.count);
             for _ in 0..header.count {
@@ -77,6 +93,88 @@ pub fn parse_frame(buf: &[u8]) -> Result<(Header, Vec<[f32; 3]>, Option<Vec<u8>>
     }
 }
 
+/// Hard ceiling on any single decompressed payload, independent of what a
+/// compressed stream or header claims to inflate to. Well above any sane
+/// point-cloud frame, but small enough that a crafted header/stream can't
+/// force an unbounded allocation before `check_decompressed_len` gets a
+/// chance to reject it.
+const MAX_DECOMPRESSED_BYTES: usize = 256 * 1024 * 1024;
+
+/// Decompresses `payload` according to `header.compression` (`None` passes
+/// it through unchanged), and checks the result against `count * stride`
+/// before the layout decoder ever sees it. `count`/`stride` are validated
+/// up front so a crafted header can't force a huge eager allocation, and the
+/// decompression itself is capped so a small malicious stream can't expand
+/// to an unbounded amount of memory regardless of what the header claims.
+fn decompress_payload<'a>(header: &Header, payload: &'a [u8]) -> Result<Cow<'a, [u8]>> {
+    let expected_len = checked_expected_len(header)?;
+    match header.compression.as_deref() {
+        None => Ok(Cow::Borrowed(payload)),
+        Some("zstd") => {
+            let out = bounded_decode(zstd::stream::Decoder::new(payload)?, expected_len)?;
+            check_decompressed_len(&out, expected_len)?;
+            Ok(Cow::Owned(out))
+        }
+        Some("lz4") => {
+            let out = lz4_flex::decompress(payload, expected_len)?;
+            check_decompressed_len(&out, expected_len)?;
+            Ok(Cow::Owned(out))
+        }
+        Some("gzip") => {
+            let out = bounded_decode(flate2::read::GzDecoder::new(payload), expected_len)?;
+            check_decompressed_len(&out, expected_len)?;
+            Ok(Cow::Owned(out))
+        }
+        Some(other) => anyhow::bail!("unsupported compression algorithm: {}", other),
+    }
+}
+
+/// Validates `count`/`stride` before they're multiplied into an allocation
+/// size, rejecting headers that would overflow `usize` outright or that
+/// claim a payload larger than `MAX_DECOMPRESSED_BYTES`.
+fn checked_expected_len(header: &Header) -> Result<usize> {
+    let expected_len = header
+        .count
+        .checked_mul(header.stride)
+        .ok_or_else(|| anyhow::anyhow!("count * stride overflows"))?;
+    if expected_len > MAX_DECOMPRESSED_BYTES {
+        anyhow::bail!(
+            "count * stride = {} exceeds max decompressed payload size of {} bytes",
+            expected_len,
+            MAX_DECOMPRESSED_BYTES
+        );
+    }
+    Ok(expected_len)
+}
+
+/// Reads at most `expected_len` + a small slack out of `reader`, erroring
+/// instead of continuing to read if more comes out than that. Used so a
+/// decompression bomb (a small compressed stream claiming a huge inflated
+/// size) can't force an unbounded allocation - the reader is cut off well
+/// before it gets anywhere near `MAX_DECOMPRESSED_BYTES`.
+fn bounded_decode<R: Read>(reader: R, expected_len: usize) -> Result<Vec<u8>> {
+    const SLACK: usize = 4096;
+    let cap = expected_len.saturating_add(SLACK);
+    let mut out = Vec::with_capacity(expected_len.min(MAX_DECOMPRESSED_BYTES));
+    let mut limited = reader.take(cap as u64 + 1);
+    limited.read_to_end(&mut out)?;
+    if out.len() as u64 > cap as u64 {
+        anyhow::bail!("decompressed payload exceeds count * stride + slack; rejecting as oversized");
+    }
+    Ok(out)
+}
+
+fn check_decompressed_len(buf: &[u8], expected: usize) -> Result<()> {
+    if buf.len() != expected {
+        anyhow::bail!(
+            "decompressed payload is {} bytes, expected count * stride = {}",
+            buf.len(),
+            expected
+        );
+    }
+    Ok(())
+}
+
 /// Parse a text WebSocket JSON message of the form:
 /// { "type": "lidar", "data": [ points_array, colors_array ] }
 /// where points_array = [[x,y,z], ...] and colors_array = [[r,g,b], ...]
@@ -139,21 +237,222 @@ pub fn to_lidar_frame_from_parsed(header: &Header, points: Vec<[f32;3]>, confs:
     LidarFrame {
         points,
         colors,
+        color_source: ColorSource::Confidence,
         timestamp: header.timestamp,
         frame_id: header.frame_id.clone(),
     }
 }
 
-/// Build from JSON-parsed points/colors
+/// Build from JSON-parsed points/colors. Colors here are whatever RGB the
+/// client sent, not a confidence byte, so they're tagged `ColorSource::Rgb`.
 pub fn to_lidar_frame_from_json(points: Vec<[f32;3]>, colors: Option<Vec<[u8;3]>>) -> LidarFrame {
     LidarFrame {
         points,
         colors,
+        color_source: ColorSource::Rgb,
         timestamp: None,
         frame_id: None,
     }
 }
 
+/// A single chunk's worth of decoded points/confidences, keyed by `seq` once
+/// buffered inside a `PartialFrame`.
+type ChunkData = (Vec<[f32; 3]>, Option<Vec<u8>>);
+
+/// Approximate heap bytes held by one chunk's points/confidences, used to
+/// bound `PartialFrame::buffered_bytes`.
+fn chunk_len(chunk: &ChunkData) -> usize {
+    let (points, confs) = chunk;
+    points.len() * std::mem::size_of::<[f32; 3]>() + confs.as_ref().map_or(0, |c| c.len())
+}
+
+/// Chunks collected so far for one `frame_id`, waiting for the chunk whose
+/// `is_last_chunk` is true and for every preceding `seq` to be present.
+struct PartialFrame {
+    chunks: BTreeMap<u32, ChunkData>,
+    last_seq: Option<u32>,
+    timestamp: Option<u64>,
+    frame_id: Option<String>,
+    last_seen: Instant,
+    /// Running total of `chunk_len` across `chunks`, kept up to date on
+    /// insert/overwrite so `FrameAssembler::enforce_limits` doesn't have to
+    /// re-walk every chunk of every partial on each call.
+    buffered_bytes: usize,
+}
+
+impl PartialFrame {
+    fn new(header: &Header) -> Self {
+        PartialFrame {
+            chunks: BTreeMap::new(),
+            last_seq: None,
+            timestamp: header.timestamp,
+            frame_id: header.frame_id.clone(),
+            last_seen: Instant::now(),
+            buffered_bytes: 0,
+        }
+    }
+
+    /// Inserts/overwrites the chunk at `seq`, keeping `buffered_bytes` in sync.
+    fn insert_chunk(&mut self, seq: u32, chunk: ChunkData) {
+        let new_bytes = chunk_len(&chunk);
+        let old = self.chunks.insert(seq, chunk);
+        let old_bytes = old.as_ref().map_or(0, chunk_len);
+        self.buffered_bytes = self.buffered_bytes + new_bytes - old_bytes;
+    }
+
+    /// True once the final chunk has arrived and every seq from 0 up to it is present.
+    fn is_complete(&self) -> bool {
+        match self.last_seq {
+            Some(last) => (0..=last).all(|seq| self.chunks.contains_key(&seq)),
+            None => false,
+        }
+    }
+
+    fn into_frame(self) -> LidarFrame {
+        let mut points = Vec::new();
+        let mut confs: Option<Vec<u8>> = None;
+        for (_, (p, c)) in self.chunks {
+            points.extend(p);
+            match (&mut confs, c) {
+                (Some(acc), Some(c)) => acc.extend(c),
+                (None, Some(c)) => confs = Some(c),
+                _ => {}
+            }
+        }
+        let colors = confs.map(|cs| cs.into_iter().map(|c| [c, c, c]).collect());
+        LidarFrame {
+            points,
+            colors,
+            color_source: ColorSource::Confidence,
+            timestamp: self.timestamp,
+            frame_id: self.frame_id,
+        }
+    }
+}
+
+/// Default cap on the number of `frame_id`s concurrently being reassembled.
+const DEFAULT_MAX_PARTIALS: usize = 64;
+
+/// Default cap on total bytes buffered across all partials, per assembler
+/// (i.e. per connection).
+const DEFAULT_MAX_BUFFERED_BYTES: usize = 64 * 1024 * 1024;
+
+/// Reassembles point-cloud frames that Unity splits across multiple chunked
+/// WebSocket messages, using `frame_id`/`seq`/`is_last_chunk` from the `Header`.
+///
+/// Single-chunk frames (no `frame_id`/`seq`) pass straight through. Chunked
+/// frames are buffered per `frame_id` until the last chunk arrives and every
+/// preceding `seq` is accounted for; duplicate seqs overwrite the earlier
+/// chunk, a fresh `seq == 0` for a `frame_id` that already has a partial
+/// replaces it outright, and partials older than `ttl` are evicted so a
+/// client that stops mid-stream can't leak memory forever. Between `ttl`
+/// sweeps, `max_partials`/`max_buffered_bytes` also bound how many
+/// frame_ids (or how many bytes) can be buffered at once: once over either
+/// limit, the least-recently-touched partials are evicted immediately,
+/// oldest first, so a client that keeps opening new frame_ids (or sends one
+/// huge one) without ever finishing can't grow memory unbounded.
+pub struct FrameAssembler {
+    partials: HashMap<String, PartialFrame>,
+    ttl: Duration,
+    max_partials: usize,
+    max_buffered_bytes: usize,
+}
+
+impl FrameAssembler {
+    pub fn new() -> Self {
+        Self::with_ttl(Duration::from_secs(5))
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self::with_limits(ttl, DEFAULT_MAX_PARTIALS, DEFAULT_MAX_BUFFERED_BYTES)
+    }
+
+    pub fn with_limits(ttl: Duration, max_partials: usize, max_buffered_bytes: usize) -> Self {
+        FrameAssembler {
+            partials: HashMap::new(),
+            ttl,
+            max_partials,
+            max_buffered_bytes,
+        }
+    }
+
+    /// Feed one parsed chunk in. Returns `Some(LidarFrame)` once the frame it
+    /// belongs to is complete, `None` while more chunks are still expected.
+    pub fn ingest(
+        &mut self,
+        header: &Header,
+        points: Vec<[f32; 3]>,
+        confs: Option<Vec<u8>>,
+    ) -> Option<LidarFrame> {
+        self.evict_stale();
+
+        let (frame_id, seq) = match (&header.frame_id, header.seq) {
+            (Some(id), Some(seq)) => (id.clone(), seq),
+            // No chunking info: treat as a complete frame on its own.
+            _ => return Some(to_lidar_frame_from_parsed(header, points, confs)),
+        };
+
+        // A fresh first chunk for a frame_id that already has a partial means
+        // the previous attempt never finished; start over.
+        if seq == 0 {
+            self.partials.remove(&frame_id);
+        }
+
+        let partial = self
+            .partials
+            .entry(frame_id.clone())
+            .or_insert_with(|| PartialFrame::new(header));
+        partial.last_seen = Instant::now();
+        partial.insert_chunk(seq, (points, confs));
+        if header.is_last_chunk.unwrap_or(false) {
+            partial.last_seq = Some(seq);
+        }
+
+        if partial.is_complete() {
+            let partial = self.partials.remove(&frame_id).unwrap();
+            return Some(partial.into_frame());
+        }
+
+        self.enforce_limits();
+        None
+    }
+
+    /// Drop partial frames that haven't seen a new chunk within `ttl`.
+    fn evict_stale(&mut self) {
+        let ttl = self.ttl;
+        self.partials
+            .retain(|_, partial| partial.last_seen.elapsed() < ttl);
+    }
+
+    fn total_buffered_bytes(&self) -> usize {
+        self.partials.values().map(|p| p.buffered_bytes).sum()
+    }
+
+    /// Evicts the least-recently-touched partials, oldest first, until both
+    /// `max_partials` and `max_buffered_bytes` are satisfied.
+    fn enforce_limits(&mut self) {
+        while self.partials.len() > self.max_partials
+            || self.total_buffered_bytes() > self.max_buffered_bytes
+        {
+            let Some(oldest_id) = self
+                .partials
+                .iter()
+                .min_by_key(|(_, p)| p.last_seen)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            self.partials.remove(&oldest_id);
+        }
+    }
+}
+
+impl Default for FrameAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +497,117 @@ mod tests {
         assert!((pts[3][0] - 3.1).abs() < 1e-6);
     }
 
+    fn make_compressed_frame(count: usize, compression: &str) -> Vec<u8> {
+        let header = serde_json::json!({
+            "version":"1.0",
+            "type":"point_cloud",
+            "frame_id":"t1",
+            "timestamp":1700000000000u64,
+            "count":count,
+            "layout":"float32_xyz",
+            "stride":12,
+            "endianness":"le",
+            "seq":0,
+            "is_last_chunk": true,
+            "compression": compression
+        });
+        let header_bytes = header.to_string().into_bytes();
+
+        let mut raw = Vec::new();
+        for i in 0..count {
+            raw.write_f32::<LittleEndian>(i as f32 + 0.1).unwrap();
+            raw.write_f32::<LittleEndian>(i as f32 + 0.2).unwrap();
+            raw.write_f32::<LittleEndian>(i as f32 + 0.3).unwrap();
+        }
+        let payload = match compression {
+            "zstd" => zstd::stream::encode_all(raw.as_slice(), 0).unwrap(),
+            "lz4" => lz4_flex::compress(&raw),
+            "gzip" => {
+                use std::io::Write;
+                let mut enc = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                enc.write_all(&raw).unwrap();
+                enc.finish().unwrap()
+            }
+            _ => raw.clone(),
+        };
+
+        let mut out = Vec::new();
+        out.write_u32::<LittleEndian>(header_bytes.len() as u32).unwrap();
+        out.extend_from_slice(&header_bytes);
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    #[test]
+    fn test_parse_zstd_compressed() {
+        let buf = make_compressed_frame(10, "zstd");
+        let (_h, pts, _confs) = parse_frame(&buf).unwrap();
+        assert_eq!(pts.len(), 10);
+        assert!((pts[3][0] - 3.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_lz4_compressed() {
+        let buf = make_compressed_frame(10, "lz4");
+        let (_h, pts, _confs) = parse_frame(&buf).unwrap();
+        assert_eq!(pts.len(), 10);
+        assert!((pts[3][0] - 3.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_gzip_compressed() {
+        let buf = make_compressed_frame(10, "gzip");
+        let (_h, pts, _confs) = parse_frame(&buf).unwrap();
+        assert_eq!(pts.len(), 10);
+        assert!((pts[3][0] - 3.1).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_unknown_compression_errors() {
+        let buf = make_compressed_frame(1, "brotli");
+        assert!(parse_frame(&buf).is_err());
+    }
+
+    #[test]
+    fn test_zstd_bomb_is_rejected_not_fully_decoded() {
+        // A small zstd stream that inflates to far more than count * stride
+        // claims should be cut off, not decoded in full and then rejected.
+        let raw = vec![0u8; 64 * 1024 * 1024];
+        let payload = zstd::stream::encode_all(raw.as_slice(), 0).unwrap();
+        let header = Header {
+            version: "1.0".into(),
+            msg_type: "point_cloud".into(),
+            frame_id: Some("bomb".into()),
+            timestamp: None,
+            count: 10,
+            layout: "float32_xyz".into(),
+            stride: 12,
+            endianness: Some("le".into()),
+            seq: Some(0),
+            is_last_chunk: Some(true),
+            compression: Some("zstd".into()),
+        };
+        assert!(decompress_payload(&header, &payload).is_err());
+    }
+
+    #[test]
+    fn test_oversized_header_is_rejected_before_allocating() {
+        let header = Header {
+            version: "1.0".into(),
+            msg_type: "point_cloud".into(),
+            frame_id: None,
+            timestamp: None,
+            count: usize::MAX,
+            layout: "float32_xyz".into(),
+            stride: 12,
+            endianness: None,
+            seq: None,
+            is_last_chunk: None,
+            compression: None,
+        };
+        assert!(checked_expected_len(&header).is_err());
+    }
+
     #[test]
     fn test_parse_lidar_json() {
         let msg = serde_json::json!({
@@ -215,5 +625,114 @@ mod tests {
         assert_eq!(cols[1], [0,255,0]);
         assert!((pts[1][2] - 6.0).abs() < 1e-6);
     }
+
+    fn make_header(frame_id: &str, seq: u32, count: usize, is_last_chunk: bool) -> Header {
+        Header {
+            version: "1.0".to_string(),
+            msg_type: "point_cloud".to_string(),
+            frame_id: Some(frame_id.to_string()),
+            timestamp: Some(1700000000000),
+            count,
+            layout: "float32_xyz".to_string(),
+            stride: 12,
+            endianness: Some("le".to_string()),
+            seq: Some(seq),
+            is_last_chunk: Some(is_last_chunk),
+            compression: None,
+        }
+    }
+
+    #[test]
+    fn test_assembler_reorders_and_completes() {
+        let mut asm = FrameAssembler::new();
+        let h0 = make_header("f1", 0, 3, false);
+        let h1 = make_header("f1", 1, 3, false);
+        let h2 = make_header("f1", 2, 3, true);
+        assert!(asm.ingest(&h0, vec![[0.0, 0.0, 0.0], [1.0, 1.0, 1.0]], None).is_none());
+        // The last chunk arrives before seq 1; the frame still comes out in seq order.
+        assert!(asm.ingest(&h2, vec![[3.0, 3.0, 3.0]], None).is_none());
+        let frame = asm.ingest(&h1, vec![[2.0, 2.0, 2.0]], None).unwrap();
+        assert_eq!(frame.points.len(), 4);
+        assert_eq!(frame.points[2], [2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_assembler_duplicate_seq_overwrites() {
+        let mut asm = FrameAssembler::new();
+        let h0 = make_header("f1", 0, 1, false);
+        let h0_dup = make_header("f1", 0, 1, false);
+        let h1 = make_header("f1", 1, 1, true);
+        assert!(asm.ingest(&h0, vec![[0.0, 0.0, 0.0]], None).is_none());
+        assert!(asm.ingest(&h0_dup, vec![[9.0, 9.0, 9.0]], None).is_none());
+        let frame = asm.ingest(&h1, vec![[1.0, 1.0, 1.0]], None).unwrap();
+        assert_eq!(frame.points, vec![[9.0, 9.0, 9.0], [1.0, 1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_assembler_restart_evicts_stale_partial() {
+        let mut asm = FrameAssembler::new();
+        let h0 = make_header("f1", 0, 1, false);
+        assert!(asm.ingest(&h0, vec![[0.0, 0.0, 0.0]], None).is_none());
+        // A new seq==0 for the same frame_id means the old partial never finished.
+        let h0_restart = make_header("f1", 0, 1, false);
+        let h1 = make_header("f1", 1, 1, true);
+        assert!(asm.ingest(&h0_restart, vec![[5.0, 5.0, 5.0]], None).is_none());
+        let frame = asm.ingest(&h1, vec![[6.0, 6.0, 6.0]], None).unwrap();
+        assert_eq!(frame.points, vec![[5.0, 5.0, 5.0], [6.0, 6.0, 6.0]]);
+    }
+
+    #[test]
+    fn test_assembler_passthrough_without_chunk_info() {
+        let mut asm = FrameAssembler::new();
+        let mut header = make_header("f1", 0, 1, false);
+        header.frame_id = None;
+        header.seq = None;
+        let frame = asm.ingest(&header, vec![[1.0, 2.0, 3.0]], None).unwrap();
+        assert_eq!(frame.points, vec![[1.0, 2.0, 3.0]]);
+    }
+
+    #[test]
+    fn test_assembler_evicts_after_ttl_expires() {
+        let mut asm = FrameAssembler::with_ttl(Duration::from_millis(10));
+        let h0 = make_header("f1", 0, 2, false);
+        assert!(asm.ingest(&h0, vec![[0.0, 0.0, 0.0]], None).is_none());
+        std::thread::sleep(Duration::from_millis(20));
+        // Feeding an unrelated frame_id runs evict_stale and should drop "f1".
+        let other = make_header("f2", 0, 1, false);
+        assert!(asm.ingest(&other, vec![[9.0, 9.0, 9.0]], None).is_none());
+        // "f1" was evicted, so its final chunk starts a brand new partial
+        // rather than completing the one from before the sleep.
+        let h1 = make_header("f1", 1, 2, true);
+        assert!(asm.ingest(&h1, vec![[1.0, 1.0, 1.0]], None).is_none());
+    }
+
+    #[test]
+    fn test_assembler_enforces_max_partials() {
+        let mut asm = FrameAssembler::with_limits(Duration::from_secs(5), 2, usize::MAX);
+        let h_a = make_header("a", 0, 1, false);
+        let h_b = make_header("b", 0, 1, false);
+        let h_c = make_header("c", 0, 1, false);
+        assert!(asm.ingest(&h_a, vec![[0.0, 0.0, 0.0]], None).is_none());
+        assert!(asm.ingest(&h_b, vec![[0.0, 0.0, 0.0]], None).is_none());
+        // Over the cap of 2 partials: the oldest ("a") should be evicted.
+        assert!(asm.ingest(&h_c, vec![[0.0, 0.0, 0.0]], None).is_none());
+        // "a"'s completing chunk now starts a fresh partial instead of completing it.
+        let h_a_last = make_header("a", 1, 2, true);
+        assert!(asm.ingest(&h_a_last, vec![[1.0, 1.0, 1.0]], None).is_none());
+    }
+
+    #[test]
+    fn test_assembler_enforces_max_buffered_bytes() {
+        // One point is 12 bytes; cap low enough that a second partial evicts the first.
+        let mut asm = FrameAssembler::with_limits(Duration::from_secs(5), usize::MAX, 12);
+        let h_a = make_header("a", 0, 2, false);
+        let h_b = make_header("b", 0, 2, false);
+        assert!(asm.ingest(&h_a, vec![[0.0, 0.0, 0.0]], None).is_none());
+        assert!(asm.ingest(&h_b, vec![[0.0, 0.0, 0.0]], None).is_none());
+        // "a" was evicted to stay within the byte cap, so its last chunk
+        // starts a fresh partial instead of completing the original one.
+        let h_a_last = make_header("a", 1, 2, true);
+        assert!(asm.ingest(&h_a_last, vec![[1.0, 1.0, 1.0]], None).is_none());
+    }
 }
 
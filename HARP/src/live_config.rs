@@ -0,0 +1,160 @@
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+use crate::transform::{AffineTransformer, IntensityTransformer, TransformPipeline};
+
+/// Live-reloadable knobs for the transform pipeline and viewer. Held behind
+/// an `ArcSwap` so a background poller (e.g. the Redis integration below)
+/// can publish a new value atomically and WebSocket handler threads /
+/// the viewer pick it up on their next frame without dropping connections.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiveConfig {
+    pub matrix: [[f32; 3]; 3],
+    pub translation: [f32; 3],
+    pub downsample_limit: usize,
+    pub intensity_gamma: f32,
+}
+
+impl Default for LiveConfig {
+    fn default() -> Self {
+        LiveConfig {
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0, 0.0, 0.0],
+            downsample_limit: 10_000,
+            intensity_gamma: 1.0,
+        }
+    }
+}
+
+impl LiveConfig {
+    /// Builds the transform pipeline this config currently describes.
+    pub fn to_pipeline(&self) -> TransformPipeline {
+        let mut pipeline = TransformPipeline::new();
+        pipeline.push(Box::new(AffineTransformer::new(self.matrix, self.translation)));
+        pipeline.push(Box::new(IntensityTransformer::with_gamma(self.intensity_gamma)));
+        pipeline
+    }
+}
+
+pub type SharedLiveConfig = Arc<ArcSwap<LiveConfig>>;
+
+pub fn shared_default() -> SharedLiveConfig {
+    Arc::new(ArcSwap::from_pointee(LiveConfig::default()))
+}
+
+/// Optional Redis-backed polling of live config. Only compiled in with the
+/// `redis` feature so a deployment without Redis doesn't pull in the client.
+#[cfg(feature = "redis")]
+mod redis_poller {
+    use super::*;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Polls `/transform/<id>` (JSON `{"matrix": [[f32;3];3], "translation":
+    /// [f32;3]}`), `/downsample/<id>`, and `/intensity/<id>` on a background
+    /// thread, and atomically swaps `shared` whenever any of them changed
+    /// since the last poll.
+    pub fn spawn(
+        redis_url: &str,
+        id: &str,
+        shared: SharedLiveConfig,
+    ) -> redis::RedisResult<thread::JoinHandle<()>> {
+        let client = redis::Client::open(redis_url)?;
+        let mut con = client.get_connection()?;
+        let transform_key = format!("/transform/{}", id);
+        let downsample_key = format!("/downsample/{}", id);
+        let intensity_key = format!("/intensity/{}", id);
+
+        Ok(thread::spawn(move || loop {
+            let mut next = (**shared.load()).clone();
+            let mut changed = false;
+
+            if let Ok(Some(raw)) = redis::cmd("GET").arg(&transform_key).query::<Option<String>>(&mut con) {
+                if let Some((matrix, translation)) = parse_transform(&raw) {
+                    if matrix != next.matrix || translation != next.translation {
+                        next.matrix = matrix;
+                        next.translation = translation;
+                        changed = true;
+                    }
+                }
+            }
+            if let Ok(Some(raw)) = redis::cmd("GET").arg(&downsample_key).query::<Option<String>>(&mut con) {
+                if let Ok(v) = raw.trim().parse::<usize>() {
+                    if v != next.downsample_limit {
+                        next.downsample_limit = v;
+                        changed = true;
+                    }
+                }
+            }
+            if let Ok(Some(raw)) = redis::cmd("GET").arg(&intensity_key).query::<Option<String>>(&mut con) {
+                if let Ok(v) = raw.trim().parse::<f32>() {
+                    if v != next.intensity_gamma {
+                        next.intensity_gamma = v;
+                        changed = true;
+                    }
+                }
+            }
+
+            if changed {
+                shared.store(Arc::new(next));
+            }
+            thread::sleep(Duration::from_millis(500));
+        }))
+    }
+
+    fn parse_transform(raw: &str) -> Option<([[f32; 3]; 3], [f32; 3])> {
+        let v: serde_json::Value = serde_json::from_str(raw).ok()?;
+        let rows = v.get("matrix")?.as_array()?;
+        if rows.len() != 3 {
+            return None;
+        }
+        let mut matrix = [[0.0f32; 3]; 3];
+        for (i, row) in rows.iter().enumerate() {
+            let row = row.as_array()?;
+            if row.len() != 3 {
+                return None;
+            }
+            for (j, val) in row.iter().enumerate() {
+                matrix[i][j] = val.as_f64()? as f32;
+            }
+        }
+        let t = v.get("translation")?.as_array()?;
+        if t.len() != 3 {
+            return None;
+        }
+        let translation = [t[0].as_f64()? as f32, t[1].as_f64()? as f32, t[2].as_f64()? as f32];
+        Some((matrix, translation))
+    }
+}
+
+#[cfg(feature = "redis")]
+pub use redis_poller::spawn as spawn_redis_poller;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_pipeline_is_identity() {
+        let cfg = LiveConfig::default();
+        let pipeline = cfg.to_pipeline();
+        let mut frame = crate::lidar::LidarFrame {
+            points: vec![[1.0, 2.0, 3.0]],
+            colors: None,
+            color_source: crate::lidar::ColorSource::Confidence,
+            timestamp: None,
+            frame_id: None,
+        };
+        pipeline.apply(&mut frame);
+        assert_eq!(frame.points[0], [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_shared_default_roundtrips() {
+        let shared = shared_default();
+        assert_eq!(**shared.load(), LiveConfig::default());
+        let updated = LiveConfig { downsample_limit: 5_000, ..LiveConfig::default() };
+        shared.store(Arc::new(updated.clone()));
+        assert_eq!(**shared.load(), updated);
+    }
+}
@@ -1,13 +1,89 @@
 use futures_util::{ SinkExt, StreamExt };
 use std::error::Error;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, WebSocketConfig};
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
 use tokio_tungstenite::tungstenite::{ Message, Error as WsError };
-use tokio_tungstenite::accept_async;
+use tokio_tungstenite::accept_async_with_config;
 use serde_json::Value;
 
 use tokio::net::TcpListener;
 use crossbeam_channel::unbounded;
-use harp::viewer;
 use harp::lidar::LidarFrame;
+use harp::live_config::{self, SharedLiveConfig};
+use harp::sink::{spawn_fanout, EtherDreamSink, LidarSink};
+use harp::viewer::KissViewerSink;
+
+/// Builds the configured set of output sinks. The kiss3d viewer can be
+/// disabled via `LIDAR_VIEWER=0`/`false`; the EtherDream galvo/DAC sink is
+/// opt-in via `LIDAR_ETHERDREAM_ADDR` (e.g. `192.168.1.50:7765`) and
+/// `LIDAR_ETHERDREAM_HZ` (default 20).
+fn build_sinks(live_config: &SharedLiveConfig) -> Vec<Box<dyn LidarSink>> {
+    let mut sinks: Vec<Box<dyn LidarSink>> = Vec::new();
+
+    let viewer_enabled = std::env::var("LIDAR_VIEWER")
+        .map(|v| !(v == "0" || v.to_lowercase() == "false"))
+        .unwrap_or(true);
+    if viewer_enabled {
+        sinks.push(Box::new(KissViewerSink::spawn(live_config.clone())));
+    } else {
+        println!("LIDAR viewer disabled via LIDAR_VIEWER env var");
+    }
+
+    if let Ok(addr) = std::env::var("LIDAR_ETHERDREAM_ADDR") {
+        let hz = std::env::var("LIDAR_ETHERDREAM_HZ")
+            .ok()
+            .and_then(|v| v.parse::<f32>().ok())
+            .unwrap_or(20.0);
+        match EtherDreamSink::spawn(&addr, hz) {
+            Ok(sink) => sinks.push(Box::new(sink)),
+            Err(e) => eprintln!("Failed to start EtherDream sink at {}: {}", addr, e),
+        }
+    }
+
+    sinks
+}
+
+/// Starts the optional Redis-backed live config poller when the `redis`
+/// feature is enabled and `LIDAR_REDIS_URL` is set; otherwise the config
+/// stays at its static default for the lifetime of the process.
+#[cfg(feature = "redis")]
+fn maybe_spawn_redis_poller(live_config: &SharedLiveConfig) {
+    let Ok(redis_url) = std::env::var("LIDAR_REDIS_URL") else {
+        return;
+    };
+    let id = std::env::var("LIDAR_REDIS_ID").unwrap_or_else(|_| "default".to_string());
+    match live_config::spawn_redis_poller(&redis_url, &id, live_config.clone()) {
+        Ok(_handle) => println!("Live config: polling Redis at {} for id={}", redis_url, id),
+        Err(e) => eprintln!("Failed to start Redis live-config poller: {}", e),
+    }
+}
+
+#[cfg(not(feature = "redis"))]
+fn maybe_spawn_redis_poller(_live_config: &SharedLiveConfig) {}
+
+/// Default cap on a single WebSocket message/frame, overridable via env vars
+/// so operators can raise or lower the backpressure bound without a rebuild.
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Build the server's `WebSocketConfig` from `LIDAR_MAX_MESSAGE_SIZE` /
+/// `LIDAR_MAX_FRAME_SIZE` (bytes), defaulting to 64 MiB so a malformed or
+/// malicious client can't stream an unbounded payload and OOM the server.
+fn websocket_config() -> WebSocketConfig {
+    let max_message_size = env_size("LIDAR_MAX_MESSAGE_SIZE", DEFAULT_MAX_MESSAGE_SIZE);
+    let max_frame_size = env_size("LIDAR_MAX_FRAME_SIZE", DEFAULT_MAX_MESSAGE_SIZE);
+    WebSocketConfig {
+        max_message_size: Some(max_message_size),
+        max_frame_size: Some(max_frame_size),
+        ..WebSocketConfig::default()
+    }
+}
+
+fn env_size(var: &str, default: usize) -> usize {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(default)
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -15,22 +91,25 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let listener = TcpListener::bind(addr).await?;
     println!("WebSocket server running on {}", addr);
 
-    // Create an in-process channel. Spawn the viewer thread that consumes LidarFrames
-    // unless the environment disables it via LIDAR_VIEWER=0 or LIDAR_VIEWER=false.
+    let ws_config = websocket_config();
+    println!(
+        "WebSocket limits: max_message_size={:?} max_frame_size={:?}",
+        ws_config.max_message_size, ws_config.max_frame_size
+    );
+    let live_config = live_config::shared_default();
+    maybe_spawn_redis_poller(&live_config);
+
+    // Create an in-process channel and fan every frame out to all configured sinks.
     let (tx, rx) = unbounded::<LidarFrame>();
-    let viewer_enabled = std::env::var("LIDAR_VIEWER").map(|v| !(v == "0" || v.to_lowercase() == "false")).unwrap_or(true);
-    let _viewer_handle = if viewer_enabled {
-        Some(viewer::spawn_viewer(rx))
-    } else {
-        println!("LIDAR viewer disabled via LIDAR_VIEWER env var");
-        None
-    };
+    let sinks = build_sinks(&live_config);
+    let _fanout_handle = spawn_fanout(rx, sinks);
 
     loop {
         let (stream, peer_addr) = listener.accept().await?;
         let tx = tx.clone();
+        let live_config = live_config.clone();
         tokio::spawn(async move {
-            if let Err(e) = handle_connection(stream, tx).await {
+            if let Err(e) = handle_connection(stream, tx, ws_config, live_config).await {
                 // Ignore common client-side connection resets that don't perform a close handshake
                 let is_ignored = match &e {
                     WsError::Protocol(p) =>
@@ -48,9 +127,14 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 }
 
-async fn handle_connection(stream: tokio::net::TcpStream, tx: crossbeam_channel::Sender<LidarFrame>) -> Result<(), WsError> {
+async fn handle_connection(
+    stream: tokio::net::TcpStream,
+    tx: crossbeam_channel::Sender<LidarFrame>,
+    ws_config: WebSocketConfig,
+    live_config: SharedLiveConfig,
+) -> Result<(), WsError> {
     let peer = stream.peer_addr().ok();
-    let ws_stream = accept_async(stream).await?;
+    let ws_stream = accept_async_with_config(stream, Some(ws_config)).await?;
     if let Some(p) = peer {
         println!("Unity connected: {}", p);
     } else {
@@ -58,9 +142,25 @@ async fn handle_connection(stream: tokio::net::TcpStream, tx: crossbeam_channel:
     }
 
     let (mut write, mut read) = ws_stream.split();
+    // Each connection reassembles its own chunked frames; frame_ids don't cross connections.
+    let mut assembler = harp::lidar::FrameAssembler::new();
 
     while let Some(msg) = read.next().await {
-        let msg = msg?;
+        let msg = match msg {
+            Ok(m) => m,
+            Err(WsError::Capacity(_)) => {
+                eprintln!("Rejecting oversized frame from {:?}", peer);
+                let _ = write.send(Message::Text("ACK error=frame_too_large".into())).await;
+                let _ = write
+                    .send(Message::Close(Some(CloseFrame {
+                        code: CloseCode::Size,
+                        reason: "frame too large".into(),
+                    })))
+                    .await;
+                break;
+            }
+            Err(e) => return Err(e),
+        };
         match msg {
             Message::Text(text) => {
                 // Try to parse as a JSON envelope and specifically support `"type":"lidar"` messages
@@ -70,7 +170,8 @@ async fn handle_connection(stream: tokio::net::TcpStream, tx: crossbeam_channel:
                         let ack = format!("ACK lidar points={}", pts.len());
                         let _ = write.send(Message::Text(ack.into())).await?;
                         // forward to in-process viewer
-                        let lf = harp::lidar::to_lidar_frame_from_json(pts, maybe_colors);
+                        let mut lf = harp::lidar::to_lidar_frame_from_json(pts, maybe_colors);
+                        live_config.load().to_pipeline().apply(&mut lf);
                         let _ = tx.send(lf);
                     }
                     Err(_) => {
@@ -102,9 +203,11 @@ async fn handle_connection(stream: tokio::net::TcpStream, tx: crossbeam_channel:
                         // For now send a short ack indicating count
                         let ack = format!("ACK points={}", pts.len());
                         let _ = write.send(Message::Text(ack.into())).await?;
-                        // forward to in-process viewer
-                        let lf = harp::lidar::to_lidar_frame_from_parsed(&hdr, pts, maybe_confs);
-                        let _ = tx.send(lf);
+                        // only forward once the assembler has a complete frame (chunks buffer otherwise)
+                        if let Some(mut lf) = assembler.ingest(&hdr, pts, maybe_confs) {
+                            live_config.load().to_pipeline().apply(&mut lf);
+                            let _ = tx.send(lf);
+                        }
                     }
                     Err(e) => {
                         eprintln!("Binary parse error: {}", e);
@@ -0,0 +1,145 @@
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::net::UdpSocket;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crate::lidar::LidarFrame;
+
+/// A consumer of fully processed `LidarFrame`s (post-transform). The server
+/// fans each complete frame out to every configured sink.
+pub trait LidarSink: Send {
+    fn consume(&mut self, frame: &LidarFrame);
+}
+
+/// Drains `rx` on the calling thread and hands every frame to each sink in
+/// turn. Sinks that need their own output cadence (a render loop, a
+/// fixed-rate device feed) run their own background thread internally and
+/// treat `consume` as "here's the latest frame", not "render now".
+pub fn run_fanout(rx: crossbeam_channel::Receiver<LidarFrame>, mut sinks: Vec<Box<dyn LidarSink>>) {
+    for frame in rx.iter() {
+        for sink in sinks.iter_mut() {
+            sink.consume(&frame);
+        }
+    }
+}
+
+pub fn spawn_fanout(
+    rx: crossbeam_channel::Receiver<LidarFrame>,
+    sinks: Vec<Box<dyn LidarSink>>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || run_fanout(rx, sinks))
+}
+
+/// Streams frames to a 2D vector/galvo DAC device (EtherDream-style) over
+/// UDP at a fixed output rate, independent of how fast frames arrive.
+/// Each point is projected to 2D (x, y; z is dropped) and mapped into the
+/// signed 16-bit DAC range, with per-point intensity taken from `colors`.
+pub struct EtherDreamSink {
+    latest: Arc<Mutex<Option<LidarFrame>>>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl EtherDreamSink {
+    /// Connects a UDP socket to `addr` and starts a background thread that
+    /// sends the most recently `consume`d frame `output_hz` times a second.
+    pub fn spawn(addr: &str, output_hz: f32) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(addr)?;
+        let latest: Arc<Mutex<Option<LidarFrame>>> = Arc::new(Mutex::new(None));
+        let latest_bg = latest.clone();
+        let period = Duration::from_secs_f32(1.0 / output_hz.max(1.0));
+
+        let handle = thread::spawn(move || loop {
+            // Clone rather than take: the device needs the last frame
+            // re-sent every tick to keep the image visible, not just once
+            // per `consume` (input rate is generally lower than `output_hz`).
+            let frame = latest_bg.lock().unwrap().clone();
+            if let Some(frame) = frame {
+                if let Err(e) = send_frame(&socket, &frame) {
+                    eprintln!("EtherDream sink: send error: {}", e);
+                }
+            }
+            thread::sleep(period);
+        });
+
+        Ok(EtherDreamSink { latest, _handle: handle })
+    }
+}
+
+impl LidarSink for EtherDreamSink {
+    fn consume(&mut self, frame: &LidarFrame) {
+        *self.latest.lock().unwrap() = Some(frame.clone());
+    }
+}
+
+/// Emits `(x, y, intensity)` per point. `intensity` is the per-point
+/// color/confidence byte (`colors[i][0]`, already derived from confidence
+/// or client RGB upstream), so a dim/blank point already produces a low
+/// intensity byte here - but there's no separate blanking bit, so a real
+/// EtherDream-style full-cutoff between discontinuous strokes (as opposed
+/// to a dim beam) isn't encoded. Revisit if a device needs hard blanking.
+fn send_frame(socket: &UdpSocket, frame: &LidarFrame) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(frame.points.len() * 5);
+    for (i, p) in frame.points.iter().enumerate() {
+        let x = project_to_dac(p[0]);
+        let y = project_to_dac(p[1]);
+        let intensity = frame
+            .colors
+            .as_ref()
+            .and_then(|colors| colors.get(i))
+            .map(|c| c[0])
+            .unwrap_or(u8::MAX);
+        buf.write_i16::<LittleEndian>(x)?;
+        buf.write_i16::<LittleEndian>(y)?;
+        buf.push(intensity);
+    }
+    socket.send(&buf)?;
+    Ok(())
+}
+
+/// Maps a coordinate assumed to already be normalized to roughly `-1.0..1.0`
+/// (by the transform pipeline) into the signed 16-bit DAC range, clamping
+/// rather than overflowing if a point falls outside that range.
+fn project_to_dac(v: f32) -> i16 {
+    let clamped = v.clamp(-1.0, 1.0);
+    (clamped * i16::MAX as f32) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_to_dac_clamps() {
+        assert_eq!(project_to_dac(0.0), 0);
+        assert_eq!(project_to_dac(1.0), i16::MAX);
+        assert_eq!(project_to_dac(2.0), i16::MAX);
+        assert_eq!(project_to_dac(-2.0), -i16::MAX);
+    }
+
+    #[test]
+    fn test_ether_dream_resends_last_frame_every_tick() {
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        listener.set_read_timeout(Some(Duration::from_millis(500))).unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut sink = EtherDreamSink::spawn(&addr.to_string(), 40.0).unwrap();
+        let frame = LidarFrame {
+            points: vec![[0.0, 0.0, 0.0]],
+            colors: None,
+            color_source: crate::lidar::ColorSource::Confidence,
+            timestamp: None,
+            frame_id: None,
+        };
+        // Only one consume: the background thread must resend this same
+        // frame on its own cadence, not go silent after the first tick.
+        sink.consume(&frame);
+
+        let mut buf = [0u8; 64];
+        let (n1, _) = listener.recv_from(&mut buf).unwrap();
+        let (n2, _) = listener.recv_from(&mut buf).unwrap();
+        assert_eq!(n1, 5);
+        assert_eq!(n2, 5);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::lidar::{ColorSource, LidarFrame};
+
+/// A single step in the transform pipeline applied to every `LidarFrame`
+/// between parsing and `tx.send`. New transforms (axis swap, clipping box,
+/// ...) just implement this and get pushed onto a `TransformPipeline`.
+pub trait Transformer: Send {
+    fn apply(&self, frame: &mut LidarFrame);
+}
+
+/// Rotates/scales points by a 3x3 matrix and then translates them, in that
+/// order: `p' = M * p + t`.
+pub struct AffineTransformer {
+    pub matrix: [[f32; 3]; 3],
+    pub translation: [f32; 3],
+}
+
+impl AffineTransformer {
+    pub fn identity() -> Self {
+        AffineTransformer {
+            matrix: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            translation: [0.0, 0.0, 0.0],
+        }
+    }
+
+    pub fn new(matrix: [[f32; 3]; 3], translation: [f32; 3]) -> Self {
+        AffineTransformer { matrix, translation }
+    }
+}
+
+impl Transformer for AffineTransformer {
+    fn apply(&self, frame: &mut LidarFrame) {
+        let m = self.matrix;
+        let t = self.translation;
+        for p in frame.points.iter_mut() {
+            let [x, y, z] = *p;
+            *p = [
+                m[0][0] * x + m[0][1] * y + m[0][2] * z + t[0],
+                m[1][0] * x + m[1][1] * y + m[1][2] * z + t[1],
+                m[2][0] * x + m[2][1] * y + m[2][2] * z + t[2],
+            ];
+        }
+    }
+}
+
+/// Remaps per-point confidence into an RGB ramp. Confidence-derived colors
+/// come in from `lidar::to_lidar_frame_from_parsed` as grayscale (`[c, c,
+/// c]`), so the `r` channel is read back out as the original confidence
+/// byte. A `lut` (indexed by confidence 0..=255) takes precedence over
+/// `gamma` when set, so a caller can supply an arbitrary heat-style ramp.
+/// No-op on frames whose `color_source` is `Rgb` — those channels are real
+/// client-supplied colors, not a confidence byte, and flattening them to
+/// grayscale would destroy them.
+pub struct IntensityTransformer {
+    pub gamma: f32,
+    pub lut: Option<Vec<[u8; 3]>>,
+}
+
+impl IntensityTransformer {
+    pub fn with_gamma(gamma: f32) -> Self {
+        IntensityTransformer { gamma, lut: None }
+    }
+
+    pub fn with_lut(lut: Vec<[u8; 3]>) -> Self {
+        IntensityTransformer { gamma: 1.0, lut: Some(lut) }
+    }
+}
+
+impl Transformer for IntensityTransformer {
+    fn apply(&self, frame: &mut LidarFrame) {
+        if frame.color_source != ColorSource::Confidence {
+            return;
+        }
+        let Some(colors) = frame.colors.as_mut() else {
+            return;
+        };
+        for c in colors.iter_mut() {
+            let confidence = c[0];
+            *c = match &self.lut {
+                Some(lut) => lut.get(confidence as usize).copied().unwrap_or(*c),
+                None => {
+                    let normalized = (confidence as f32 / 255.0).powf(self.gamma.max(1e-4));
+                    let v = (normalized * 255.0).round().clamp(0.0, 255.0) as u8;
+                    [v, v, v]
+                }
+            };
+        }
+    }
+}
+
+/// An ordered sequence of `Transformer`s run over every frame between
+/// parsing and hand-off to sinks.
+#[derive(Default)]
+pub struct TransformPipeline {
+    transformers: Vec<Box<dyn Transformer>>,
+}
+
+impl TransformPipeline {
+    pub fn new() -> Self {
+        TransformPipeline { transformers: Vec::new() }
+    }
+
+    pub fn push(&mut self, transformer: Box<dyn Transformer>) {
+        self.transformers.push(transformer);
+    }
+
+    pub fn apply(&self, frame: &mut LidarFrame) {
+        for transformer in &self.transformers {
+            transformer.apply(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(points: Vec<[f32; 3]>, colors: Option<Vec<[u8; 3]>>) -> LidarFrame {
+        LidarFrame { points, colors, color_source: ColorSource::Confidence, timestamp: None, frame_id: None }
+    }
+
+    #[test]
+    fn test_affine_translate_and_scale() {
+        let affine = AffineTransformer::new(
+            [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]],
+            [1.0, 0.0, 0.0],
+        );
+        let mut f = frame(vec![[1.0, 1.0, 1.0]], None);
+        affine.apply(&mut f);
+        assert_eq!(f.points[0], [3.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_identity_is_noop() {
+        let affine = AffineTransformer::identity();
+        let mut f = frame(vec![[1.0, -2.0, 3.0]], None);
+        affine.apply(&mut f);
+        assert_eq!(f.points[0], [1.0, -2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_intensity_gamma_remaps_grayscale() {
+        let intensity = IntensityTransformer::with_gamma(2.0);
+        let mut f = frame(vec![[0.0, 0.0, 0.0]], Some(vec![[128, 128, 128]]));
+        intensity.apply(&mut f);
+        let c = f.colors.unwrap()[0];
+        assert_eq!(c[0], c[1]);
+        assert_eq!(c[1], c[2]);
+        assert!(c[0] < 128);
+    }
+
+    #[test]
+    fn test_intensity_lut_overrides_gamma() {
+        let mut lut = vec![[0u8, 0, 0]; 256];
+        lut[200] = [255, 128, 0];
+        let intensity = IntensityTransformer::with_lut(lut);
+        let mut f = frame(vec![[0.0, 0.0, 0.0]], Some(vec![[200, 200, 200]]));
+        intensity.apply(&mut f);
+        assert_eq!(f.colors.unwrap()[0], [255, 128, 0]);
+    }
+
+    #[test]
+    fn test_intensity_skips_rgb_colors() {
+        let intensity = IntensityTransformer::with_gamma(1.0);
+        let mut f = frame(vec![[0.0, 0.0, 0.0]], Some(vec![[255, 0, 0]]));
+        f.color_source = ColorSource::Rgb;
+        intensity.apply(&mut f);
+        assert_eq!(f.colors.unwrap()[0], [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_pipeline_runs_in_order() {
+        let mut pipeline = TransformPipeline::new();
+        pipeline.push(Box::new(AffineTransformer::new(
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            [10.0, 0.0, 0.0],
+        )));
+        // Scaling after translating gives 20; the reverse order would give 10.
+        pipeline.push(Box::new(AffineTransformer::new(
+            [[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]],
+            [0.0, 0.0, 0.0],
+        )));
+        pipeline.push(Box::new(IntensityTransformer::with_gamma(1.0)));
+        let mut f = frame(vec![[0.0, 0.0, 0.0]], Some(vec![[100, 100, 100]]));
+        pipeline.apply(&mut f);
+        assert_eq!(f.points[0], [20.0, 0.0, 0.0]);
+    }
+}
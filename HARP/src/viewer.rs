@@ -1,34 +1,127 @@
-use crossbeam_channel::Receiver;
+use crossbeam_channel::{unbounded, Receiver, Sender};
 use kiss3d::window::Window;
 use kiss3d::pollster::block_on;
 use nalgebra::Point3;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::lidar::LidarFrame;
+use crate::live_config::SharedLiveConfig;
+use crate::sink::LidarSink;
 
-/// Spawn a thread that runs the kiss3d viewer and consumes LidarFrame values from `rx`.
+/// Default output frame rate when `LIDAR_VIEWER_FPS` isn't set. Shared by
+/// both the in-process viewer (`spawn_viewer`) and the standalone
+/// `lidar_viewer` binary so the two can't drift apart.
+pub const DEFAULT_OUTPUT_FPS: f32 = 60.0;
+/// How often the viewer loops log a stats line.
+pub const STATS_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Incoming-vs-rendered frame counters, exposed so a caller can inspect
+/// throughput without scraping log lines. Used by both `spawn_viewer` and
+/// the standalone `lidar_viewer` binary.
+#[derive(Default)]
+pub struct RenderStats {
+    frames_in: AtomicU64,
+    frames_rendered: AtomicU64,
+    frames_dropped: AtomicU64,
+}
+
+impl RenderStats {
+    pub fn frames_in(&self) -> u64 {
+        self.frames_in.load(Ordering::Relaxed)
+    }
+
+    pub fn frames_rendered(&self) -> u64 {
+        self.frames_rendered.load(Ordering::Relaxed)
+    }
+
+    pub fn frames_dropped(&self) -> u64 {
+        self.frames_dropped.load(Ordering::Relaxed)
+    }
+
+    /// Records one incoming frame.
+    pub fn record_in(&self) {
+        self.frames_in.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records one frame replaced before it was ever rendered.
+    pub fn record_dropped(&self) {
+        self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records one frame actually drawn to the window.
+    pub fn record_rendered(&self) {
+        self.frames_rendered.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub type SharedRenderStats = Arc<RenderStats>;
+
+/// Paces a loop to a target output rate, independent of how fast input
+/// arrives, by sleeping off whatever's left of the current tick period.
+/// Shared by `spawn_viewer` and the standalone `lidar_viewer` binary so
+/// both viewers pace output the same way.
+pub struct FrameRateGovernor {
+    tick_period: Duration,
+    last_tick: Instant,
+}
+
+impl FrameRateGovernor {
+    pub fn new(target_fps: f32) -> Self {
+        let tick_period = Duration::from_secs_f32(1.0 / target_fps.max(1.0));
+        FrameRateGovernor { tick_period, last_tick: Instant::now() }
+    }
+
+    pub fn pace(&mut self) {
+        let elapsed = self.last_tick.elapsed();
+        if elapsed < self.tick_period {
+            thread::sleep(self.tick_period - elapsed);
+        }
+        self.last_tick = Instant::now();
+    }
+}
+
+/// Spawn a thread that runs the kiss3d viewer and consumes LidarFrame values
+/// from `rx`, rendering at `output_fps` regardless of how fast frames arrive.
+/// `live_config.downsample_limit` is re-read per rebuilt frame so it can be
+/// changed live. Incoming/rendered/dropped counts accumulate into `stats`
+/// and get logged periodically alongside the measured input rate.
 /// Returns the JoinHandle for the spawned thread if the caller wants to join it later.
-pub fn spawn_viewer(rx: Receiver<LidarFrame>) -> std::thread::JoinHandle<()> {
+pub fn spawn_viewer(
+    rx: Receiver<LidarFrame>,
+    live_config: SharedLiveConfig,
+    output_fps: f32,
+    stats: SharedRenderStats,
+) -> std::thread::JoinHandle<()> {
     thread::spawn(move || {
         let mut window = Window::new("LiDAR Viewer (in-process)");
         let mut points_mesh = Vec::<Point3<f32>>::new();
         let mut colors_mesh = Vec::<[u8; 3]>::new();
+        let mut governor = FrameRateGovernor::new(output_fps);
+        let mut last_log = Instant::now();
+        let mut frames_in_at_last_log = 0u64;
 
         while block_on(window.render()) {
-            // Drain to latest frame
+            // Drain to latest frame; every frame replaced before this one is a drop.
             let mut latest: Option<LidarFrame> = None;
             loop {
                 match rx.try_recv() {
-                    Ok(f) => latest = Some(f),
+                    Ok(f) => {
+                        stats.record_in();
+                        if latest.replace(f).is_some() {
+                            stats.record_dropped();
+                        }
+                    }
                     Err(crossbeam_channel::TryRecvError::Empty) => break,
                     Err(crossbeam_channel::TryRecvError::Disconnected) => return,
                 }
             }
 
             if let Some(frame) = latest {
-                // downsample to a reasonable limit
-                let limit = 10000usize.min(frame.points.len());
+                // downsample to the currently configured limit
+                let limit = live_config.load().downsample_limit.min(frame.points.len());
                 points_mesh.clear();
                 colors_mesh.clear();
                 if frame.points.is_empty() { continue; }
@@ -52,9 +145,49 @@ pub fn spawn_viewer(rx: Receiver<LidarFrame>) -> std::thread::JoinHandle<()> {
                 };
                 window.draw_point(p, &color);
             }
+            stats.record_rendered();
+
+            if last_log.elapsed() >= STATS_LOG_INTERVAL {
+                let frames_in = stats.frames_in();
+                let input_hz = (frames_in - frames_in_at_last_log) as f32 / last_log.elapsed().as_secs_f32();
+                println!(
+                    "Viewer stats: input={:.1}Hz in={} rendered={} dropped={}",
+                    input_hz, frames_in, stats.frames_rendered(), stats.frames_dropped()
+                );
+                frames_in_at_last_log = frames_in;
+                last_log = Instant::now();
+            }
 
-            // small sleep to reduce busy loop when there are no frames
-            thread::sleep(Duration::from_millis(8));
+            governor.pace();
         }
     })
 }
+
+/// `LidarSink` wrapping the kiss3d viewer. The window has to be driven from
+/// its own dedicated thread (as `spawn_viewer` already does), so `consume`
+/// just forwards frames over an internal channel rather than rendering
+/// inline on the fan-out thread.
+pub struct KissViewerSink {
+    tx: Sender<LidarFrame>,
+    pub stats: SharedRenderStats,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl KissViewerSink {
+    pub fn spawn(live_config: SharedLiveConfig) -> Self {
+        let output_fps = std::env::var("LIDAR_VIEWER_FPS")
+            .ok()
+            .and_then(|v| v.parse::<f32>().ok())
+            .unwrap_or(DEFAULT_OUTPUT_FPS);
+        let (tx, rx) = unbounded();
+        let stats = SharedRenderStats::default();
+        let handle = spawn_viewer(rx, live_config, output_fps, stats.clone());
+        KissViewerSink { tx, stats, _handle: handle }
+    }
+}
+
+impl LidarSink for KissViewerSink {
+    fn consume(&mut self, frame: &LidarFrame) {
+        let _ = self.tx.send(frame.clone());
+    }
+}